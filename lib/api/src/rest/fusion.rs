@@ -0,0 +1,280 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use super::score_detail::ScoreDetailAccumulator;
+
+/// Min-max normalizes `scores` into `[0, 1]` so that differently-scaled sub-queries (e.g. cosine
+/// distance vs. BM25-style sparse scores) become comparable before fusion.
+///
+/// A result set where every score is equal (including the empty set) normalizes to all zeros,
+/// since there is no spread to scale by.
+fn min_max_normalize(scores: &[f32]) -> Vec<f32> {
+    let min = scores.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let range = max - min;
+
+    if range <= f32::EPSILON {
+        return vec![0.0; scores.len()];
+    }
+
+    scores.iter().map(|score| (score - min) / range).collect()
+}
+
+/// Min-max normalizes a branch's `(id, score)` pairs and returns an id -> normalized score
+/// lookup, alongside each id's 0-based rank (best score first) within that branch.
+///
+/// Branches are kept as sparse `(id, score)` pairs rather than dense arrays because real
+/// prefetches don't all return the same point set in the same order.
+fn normalize_branch<Id: Eq + Hash + Clone>(
+    results: &[(Id, f32)],
+) -> (HashMap<Id, f32>, HashMap<Id, usize>) {
+    let scores: Vec<f32> = results.iter().map(|(_, score)| *score).collect();
+    let normalized = min_max_normalize(&scores);
+
+    let mut order: Vec<usize> = (0..results.len()).collect();
+    order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+    let ranks = order
+        .into_iter()
+        .enumerate()
+        .map(|(rank, index)| (results[index].0.clone(), rank))
+        .collect();
+
+    let scores_by_id = results
+        .iter()
+        .zip(normalized)
+        .map(|((id, _), norm)| (id.clone(), norm))
+        .collect();
+
+    (scores_by_id, ranks)
+}
+
+/// A single named prefetch branch going into a fusion: its result set plus the label recorded in
+/// `ScoreDetail::branches` (e.g. `"semantic"`, `"sparse"`) when `with_score_details` is set.
+pub struct FusionBranch<'a, Id> {
+    pub name: &'a str,
+    pub results: &'a [(Id, f32)],
+}
+
+/// Collects the union of point ids across `branches`, each kept once, in first-seen branch order.
+fn union_ids<Id: Eq + Hash + Clone>(branches: &[FusionBranch<Id>]) -> Vec<Id> {
+    let mut seen = HashSet::new();
+    let mut ids = Vec::new();
+    for branch in branches {
+        for (id, _) in branch.results {
+            if seen.insert(id.clone()) {
+                ids.push(id.clone());
+            }
+        }
+    }
+    ids
+}
+
+/// Linearly blends any number of independently-scored branches with per-branch `weights`.
+///
+/// `final = Σ weight[i] * normalized(branch[i])`. The branches are joined by point id rather
+/// than position: real hybrid prefetches rarely return the same point set in the same order, so
+/// a point missing from a branch contributes a normalized score of `0.0` from that branch
+/// instead of being dropped or silently misaligned.
+///
+/// When `with_score_details` is set, also returns a [`ScoreDetailAccumulator`] carrying each
+/// surviving point's per-branch score/rank plus its fused score. The `with_score_details`
+/// request flag and the `score_detail` field on returned points live on `rest::schema`'s
+/// `Query`/point-result types, which aren't part of this chunk; this is the fusion-side half
+/// that feeds them.
+///
+/// Panics if `branches.len() != weights.len()`; callers control both lists and a length mismatch
+/// is a programming error, not a request-shaped one.
+pub fn weighted_fusion<Id: Eq + Hash + Clone>(
+    branches: &[FusionBranch<Id>],
+    weights: &[f32],
+    with_score_details: bool,
+) -> (Vec<(Id, f32)>, Option<ScoreDetailAccumulator<Id>>) {
+    assert_eq!(branches.len(), weights.len(), "one weight per branch");
+
+    let normalized: Vec<(HashMap<Id, f32>, HashMap<Id, usize>)> =
+        branches.iter().map(|branch| normalize_branch(branch.results)).collect();
+
+    let ids = union_ids(branches);
+    let mut accumulator = with_score_details.then(ScoreDetailAccumulator::default);
+    let mut fused = Vec::with_capacity(ids.len());
+
+    for id in ids {
+        let mut score = 0.0;
+        for ((branch, (scores, ranks)), &weight) in
+            branches.iter().zip(&normalized).zip(weights)
+        {
+            let branch_score = scores.get(&id).copied().unwrap_or(0.0);
+            score += weight * branch_score;
+
+            if let Some(accumulator) = &mut accumulator {
+                if let Some(&rank) = ranks.get(&id) {
+                    accumulator.record_branch(id.clone(), branch.name, branch_score, rank);
+                }
+            }
+        }
+
+        if let Some(accumulator) = &mut accumulator {
+            accumulator.record_fused(&id, score);
+        }
+
+        fused.push((id, score));
+    }
+
+    (fused, accumulator)
+}
+
+/// Convenience wrapper for `Fusion::Weighted { semantic_ratio }`'s exact two-branch shape:
+/// `final = ratio * normalized(semantic) + (1 - ratio) * normalized(keyword)`.
+pub fn weighted_fusion_by_ratio<Id: Eq + Hash + Clone>(
+    semantic: &[(Id, f32)],
+    keyword: &[(Id, f32)],
+    semantic_ratio: f32,
+    with_score_details: bool,
+) -> (Vec<(Id, f32)>, Option<ScoreDetailAccumulator<Id>>) {
+    let branches = [
+        FusionBranch { name: "semantic", results: semantic },
+        FusionBranch { name: "keyword", results: keyword },
+    ];
+    let weights = [semantic_ratio, 1.0 - semantic_ratio];
+    weighted_fusion(&branches, &weights, with_score_details)
+}
+
+/// Ranks a branch's `(id, score)` pairs (best score first, 0-based) without normalizing scores,
+/// since RRF only ever looks at rank.
+fn rank_branch<Id: Eq + Hash + Clone>(results: &[(Id, f32)]) -> HashMap<Id, usize> {
+    let mut order: Vec<usize> = (0..results.len()).collect();
+    order.sort_by(|&a, &b| results[b].1.total_cmp(&results[a].1));
+    order
+        .into_iter()
+        .enumerate()
+        .map(|(rank, index)| (results[index].0.clone(), rank))
+        .collect()
+}
+
+const DEFAULT_RRF_K: f32 = 60.0;
+
+/// Reciprocal Rank Fusion: `score = Σ 1 / (k + rank[i] + 1)` over the branches a point appears
+/// in, using the conventional `k = 60`. Unlike [`weighted_fusion`], RRF only cares about each
+/// branch's ranking, not its raw or normalized scores, so a point's contribution from a branch
+/// depends only on how far down that branch's list it landed.
+///
+/// Matches `Fusion::Rrf`. See [`weighted_fusion`] for the `with_score_details` and point-id-join
+/// behavior, which this shares.
+pub fn rrf_fusion<Id: Eq + Hash + Clone>(
+    branches: &[FusionBranch<Id>],
+    with_score_details: bool,
+) -> (Vec<(Id, f32)>, Option<ScoreDetailAccumulator<Id>>) {
+    let ranks: Vec<HashMap<Id, usize>> =
+        branches.iter().map(|branch| rank_branch(branch.results)).collect();
+
+    let ids = union_ids(branches);
+    let mut accumulator = with_score_details.then(ScoreDetailAccumulator::default);
+    let mut fused = Vec::with_capacity(ids.len());
+
+    for id in ids {
+        let mut score = 0.0;
+        for (branch, branch_ranks) in branches.iter().zip(&ranks) {
+            let Some(&rank) = branch_ranks.get(&id) else {
+                continue;
+            };
+
+            let branch_score = 1.0 / (DEFAULT_RRF_K + rank as f32 + 1.0);
+            score += branch_score;
+
+            if let Some(accumulator) = &mut accumulator {
+                accumulator.record_branch(id.clone(), branch.name, branch_score, rank);
+            }
+        }
+
+        if let Some(accumulator) = &mut accumulator {
+            accumulator.record_fused(&id, score);
+        }
+
+        fused.push((id, score));
+    }
+
+    (fused, accumulator)
+}
+
+/// Z-score normalizes `scores` (subtract mean, divide by standard deviation) so branches with
+/// different score distributions become comparable by how far each point sits from its branch's
+/// own average, rather than by raw magnitude.
+///
+/// A branch with zero variance (including the empty set) normalizes to all zeros, since there's
+/// no spread to scale by.
+fn z_score_normalize(scores: &[f32]) -> Vec<f32> {
+    let mean = scores.iter().sum::<f32>() / scores.len().max(1) as f32;
+    let variance =
+        scores.iter().map(|score| (score - mean).powi(2)).sum::<f32>() / scores.len().max(1) as f32;
+    let std_dev = variance.sqrt();
+
+    if std_dev <= f32::EPSILON {
+        return vec![0.0; scores.len()];
+    }
+
+    scores.iter().map(|score| (score - mean) / std_dev).collect()
+}
+
+/// Z-score normalizes a branch's `(id, score)` pairs and returns an id -> normalized score
+/// lookup, alongside each id's 0-based rank (best score first) within that branch.
+fn z_score_branch<Id: Eq + Hash + Clone>(
+    results: &[(Id, f32)],
+) -> (HashMap<Id, f32>, HashMap<Id, usize>) {
+    let scores: Vec<f32> = results.iter().map(|(_, score)| *score).collect();
+    let normalized = z_score_normalize(&scores);
+
+    let ranks = rank_branch(results);
+    let scores_by_id = results
+        .iter()
+        .zip(normalized)
+        .map(|((id, _), norm)| (id.clone(), norm))
+        .collect();
+
+    (scores_by_id, ranks)
+}
+
+/// Distribution-Based Score Fusion: z-score normalizes each branch, then sums a point's
+/// normalized scores across the branches it appears in.
+///
+/// Unlike [`weighted_fusion`]'s min-max normalization, z-scoring is sensitive to each branch's
+/// actual score distribution (not just its min/max), which matters more when branches disagree
+/// sharply on how spread out their scores are — e.g. a tight cluster of cosine scores fused with
+/// a long-tailed BM25-style sparse score.
+///
+/// Matches `Fusion::Dbsf`. See [`weighted_fusion`] for the `with_score_details` and
+/// point-id-join behavior, which this shares.
+pub fn dbsf_fusion<Id: Eq + Hash + Clone>(
+    branches: &[FusionBranch<Id>],
+    with_score_details: bool,
+) -> (Vec<(Id, f32)>, Option<ScoreDetailAccumulator<Id>>) {
+    let normalized: Vec<(HashMap<Id, f32>, HashMap<Id, usize>)> =
+        branches.iter().map(|branch| z_score_branch(branch.results)).collect();
+
+    let ids = union_ids(branches);
+    let mut accumulator = with_score_details.then(ScoreDetailAccumulator::default);
+    let mut fused = Vec::with_capacity(ids.len());
+
+    for id in ids {
+        let mut score = 0.0;
+        for (branch, (scores, ranks)) in branches.iter().zip(&normalized) {
+            let Some(&branch_score) = scores.get(&id) else {
+                continue;
+            };
+            score += branch_score;
+
+            if let Some(accumulator) = &mut accumulator {
+                if let Some(&rank) = ranks.get(&id) {
+                    accumulator.record_branch(id.clone(), branch.name, branch_score, rank);
+                }
+            }
+        }
+
+        if let Some(accumulator) = &mut accumulator {
+            accumulator.record_fused(&id, score);
+        }
+
+        fused.push((id, score));
+    }
+
+    (fused, accumulator)
+}
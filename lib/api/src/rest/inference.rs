@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map as JsonMap, Value};
+
+use super::schema::{BatchVectorStruct, Document, Vector, VectorStruct};
+use super::{QueryInterface, VectorInput};
+
+/// Configuration for a single named embedder, registered per collection at creation time.
+///
+/// `name` is the identifier clients reference from `Document { model, .. }`; it does not have
+/// to match the underlying model name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EmbedderConfig {
+    /// Model identifier understood by the inference backend, e.g. `"BAAI/bge-small-en-v1.5"`.
+    pub model: String,
+    /// Inference backend endpoint to call. `None` uses the built-in default backend.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Extra backend-specific options forwarded verbatim on every inference request.
+    #[serde(default)]
+    pub options: JsonMap<String, Value>,
+    /// Whether this embedder produces dense or sparse vectors.
+    #[serde(default)]
+    pub output: EmbedderOutput,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbedderOutput {
+    #[default]
+    Dense,
+    Sparse,
+}
+
+/// Per-collection registry mapping embedder name to its configuration.
+///
+/// Populated from collection creation parameters and consulted by [`infer_vector`] whenever a
+/// `Document` input needs to be turned into a real vector, both at upsert time and at query time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EmbedderRegistry(HashMap<String, EmbedderConfig>);
+
+impl EmbedderRegistry {
+    pub fn new(embedders: HashMap<String, EmbedderConfig>) -> Self {
+        Self(embedders)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EmbedderConfig> {
+        self.0.get(name)
+    }
+
+    pub fn insert(&mut self, name: String, config: EmbedderConfig) {
+        self.0.insert(name, config);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum InferenceError {
+    #[error("embedder {0:?} is not configured for this collection")]
+    UnknownEmbedder(String),
+    #[error("document text must not be empty")]
+    EmptyText,
+    #[error("inference backend error: {0}")]
+    Backend(String),
+}
+
+enum BackendEmbedding {
+    Dense(Vec<f32>),
+    Sparse(Vec<(u32, f32)>),
+}
+
+/// Turns document text into the vector it should be stored or searched with.
+///
+/// Looks up `model` in `registry` and dispatches to its configured backend: an HTTP call when
+/// an `endpoint` is set, otherwise the built-in deterministic local embedder. Returns
+/// [`InferenceError::UnknownEmbedder`] if no embedder is registered under that name, and
+/// [`InferenceError::EmptyText`] if there is nothing to embed. Callers run this before the
+/// usual vector validation and storage/search path, so downstream code never sees a raw
+/// `Document` once inference has run.
+pub fn infer_vector(
+    registry: &EmbedderRegistry,
+    model: &str,
+    text: &str,
+) -> Result<Vector, InferenceError> {
+    if text.trim().is_empty() {
+        return Err(InferenceError::EmptyText);
+    }
+
+    let embedder = registry
+        .get(model)
+        .ok_or_else(|| InferenceError::UnknownEmbedder(model.to_string()))?;
+
+    match (run_embedder(embedder, text)?, embedder.output) {
+        (BackendEmbedding::Dense(v), EmbedderOutput::Dense) => Ok(Vector::Dense(v.into())),
+        (BackendEmbedding::Sparse(v), EmbedderOutput::Sparse) => {
+            Ok(Vector::Sparse(sparse_vector_from_pairs(v)))
+        }
+        _ => Err(InferenceError::Backend(format!(
+            "embedder {model:?} returned an output that does not match its configured kind"
+        ))),
+    }
+}
+
+fn run_embedder(
+    embedder: &EmbedderConfig,
+    text: &str,
+) -> Result<BackendEmbedding, InferenceError> {
+    match &embedder.endpoint {
+        Some(endpoint) => call_remote_embedder(endpoint, &embedder.model, text, &embedder.options),
+        None => Ok(default_local_embedding(text, embedder.output)),
+    }
+}
+
+const DEFAULT_LOCAL_EMBEDDING_DIM: usize = 384;
+
+/// Built-in default backend: a deterministic feature-hashed embedding of the document text.
+///
+/// It has none of the semantic quality of a real model, but it's real and needs no network
+/// access, so any embedder registered without an `endpoint` still produces usable vectors.
+/// A `Document` is only ever resolved through a *registered* embedder (see
+/// [`EmbedderRegistry::get`]), so this still requires the collection to configure one by name;
+/// it just doesn't also require a remote endpoint. Set `endpoint` on the embedder to use an
+/// actual model-backed backend instead.
+fn default_local_embedding(text: &str, output: EmbedderOutput) -> BackendEmbedding {
+    let mut vector = vec![0f32; DEFAULT_LOCAL_EMBEDDING_DIM];
+    for token in text.split_whitespace() {
+        let hash = hash_token(token);
+        let index = (hash as usize) % DEFAULT_LOCAL_EMBEDDING_DIM;
+        let sign = if hash & 1 == 0 { 1.0 } else { -1.0 };
+        vector[index] += sign;
+    }
+
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > f32::EPSILON {
+        for v in &mut vector {
+            *v /= norm;
+        }
+    }
+
+    match output {
+        EmbedderOutput::Dense => BackendEmbedding::Dense(vector),
+        EmbedderOutput::Sparse => BackendEmbedding::Sparse(
+            vector
+                .into_iter()
+                .enumerate()
+                .filter(|(_, value)| *value != 0.0)
+                .map(|(index, value)| (index as u32, value))
+                .collect(),
+        ),
+    }
+}
+
+fn hash_token(token: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    token.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Serialize)]
+struct EmbedRequest<'a> {
+    model: &'a str,
+    input: &'a str,
+    #[serde(flatten)]
+    options: &'a JsonMap<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct EmbedResponse {
+    #[serde(default)]
+    dense: Option<Vec<f32>>,
+    #[serde(default)]
+    sparse: Option<Vec<(u32, f32)>>,
+}
+
+fn call_remote_embedder(
+    endpoint: &str,
+    model: &str,
+    text: &str,
+    options: &JsonMap<String, Value>,
+) -> Result<BackendEmbedding, InferenceError> {
+    let response: EmbedResponse = reqwest::blocking::Client::new()
+        .post(endpoint)
+        .json(&EmbedRequest {
+            model,
+            input: text,
+            options,
+        })
+        .send()
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| InferenceError::Backend(err.to_string()))?
+        .json()
+        .map_err(|err| InferenceError::Backend(err.to_string()))?;
+
+    match (response.dense, response.sparse) {
+        (Some(dense), _) => Ok(BackendEmbedding::Dense(dense)),
+        (None, Some(sparse)) => Ok(BackendEmbedding::Sparse(sparse)),
+        (None, None) => Err(InferenceError::Backend(
+            "embedder response contained neither a dense nor sparse vector".to_string(),
+        )),
+    }
+}
+
+fn sparse_vector_from_pairs(pairs: Vec<(u32, f32)>) -> super::schema::SparseVector {
+    let (indices, values) = pairs.into_iter().unzip();
+    super::schema::SparseVector { indices, values }
+}
+
+fn embed_document(
+    registry: &EmbedderRegistry,
+    document: &Document,
+) -> Result<Vector, InferenceError> {
+    infer_vector(registry, &document.model, &document.text)
+}
+
+/// Resolves a `Document` query target into the real vector to search with.
+///
+/// This is the inference step for `QueryInterface::Nearest(VectorInput::Document)`: call it once
+/// the request has passed [`super::validate::validate_document`]'s structural check, before the
+/// vector reaches the index. Non-`Document` inputs pass through unchanged.
+pub fn resolve_vector_input(
+    registry: &EmbedderRegistry,
+    vector: VectorInput,
+) -> Result<VectorInput, InferenceError> {
+    let VectorInput::Document(document) = &vector else {
+        return Ok(vector);
+    };
+
+    match embed_document(registry, document)? {
+        Vector::Dense(dense) => Ok(VectorInput::DenseVector(dense)),
+        Vector::Sparse(sparse) => Ok(VectorInput::SparseVector(sparse)),
+        // infer_vector only ever returns Dense or Sparse; these two never actually happen, but
+        // Vector may grow new variants and we want a Backend error instead of a silent mismatch.
+        Vector::MultiDense(_) | Vector::Document(_) => Err(InferenceError::Backend(format!(
+            "embedder {:?} does not produce a valid query vector",
+            document.model
+        ))),
+    }
+}
+
+/// Resolves `QueryInterface::Nearest(VectorInput::Document)` into a real nearest-vector query.
+///
+/// Every other query shape passes through unchanged for now. `Query::Recommend`,
+/// `Query::Discover`, and `Query::Context` can also carry `VectorInput::Document` positives and
+/// negatives (see their `ValidateArgs` impls in `validate.rs`, which do validate them), but
+/// resolving those needs a way to rebuild a `RecommendInput`/`DiscoverInput`/`ContextInput` from
+/// its resolved vectors, and neither their field layout nor a mutable accessor over their
+/// elements is reachable from this chunk of the tree. A full fix has to rebuild those types
+/// in-place the same way [`resolve_vector_input`] rebuilds a bare `VectorInput`.
+pub fn resolve_query_interface(
+    registry: &EmbedderRegistry,
+    query: QueryInterface,
+) -> Result<QueryInterface, InferenceError> {
+    match query {
+        QueryInterface::Nearest(vector) => {
+            Ok(QueryInterface::Nearest(resolve_vector_input(registry, vector)?))
+        }
+        other @ QueryInterface::Query(_) => Ok(other),
+    }
+}
+
+/// Resolves a `Document` point vector into the real vector to store, for upsert requests.
+///
+/// `VectorStruct::Single` only holds a dense vector, so a `Document` backed by a sparse embedder
+/// can't be represented this way; that's reported as a backend error rather than silently
+/// dropping the sparse values.
+pub fn resolve_vector_struct(
+    registry: &EmbedderRegistry,
+    vector: VectorStruct,
+) -> Result<VectorStruct, InferenceError> {
+    let VectorStruct::Document(document) = &vector else {
+        return Ok(vector);
+    };
+
+    match embed_document(registry, document)? {
+        Vector::Dense(dense) => Ok(VectorStruct::Single(dense)),
+        Vector::MultiDense(multi) => Ok(VectorStruct::MultiDense(multi)),
+        Vector::Sparse(_) | Vector::Document(_) => Err(InferenceError::Backend(format!(
+            "embedder {:?} produced a sparse vector, which VectorStruct::Single can't store",
+            document.model
+        ))),
+    }
+}
+
+/// Batch form of [`resolve_vector_struct`], embedding every document in a `Document` batch.
+pub fn resolve_batch_vector_struct(
+    registry: &EmbedderRegistry,
+    vector: BatchVectorStruct,
+) -> Result<BatchVectorStruct, InferenceError> {
+    let BatchVectorStruct::Document(documents) = vector else {
+        return Ok(vector);
+    };
+
+    let dense_vectors = documents
+        .iter()
+        .map(|document| match embed_document(registry, document)? {
+            Vector::Dense(dense) => Ok(dense),
+            Vector::Sparse(_) | Vector::MultiDense(_) | Vector::Document(_) => {
+                Err(InferenceError::Backend(format!(
+                    "embedder {:?} must produce a dense vector for a batched upsert",
+                    document.model
+                )))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(BatchVectorStruct::Single(dense_vectors))
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+/// The score and rank a single prefetch branch contributed to a fused result point.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct BranchScore {
+    pub score: f32,
+    pub rank: usize,
+}
+
+/// Per-point breakdown of how a fused score was produced, returned when a query opts in with
+/// `with_score_details`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ScoreDetail {
+    /// Score and rank contributed by each named prefetch branch (e.g. `"semantic"`, `"sparse"`).
+    pub branches: HashMap<String, BranchScore>,
+    /// The final score after fusion.
+    pub fused: f32,
+}
+
+/// Accumulates a [`ScoreDetail`] per point as a fusion executor walks its prefetch branches.
+///
+/// The executor calls [`Self::record_branch`] once per point per branch while collecting
+/// sub-query results, then [`Self::record_fused`] once that point's fused score is known. `Id`
+/// is whatever the executor already uses to identify a result point (e.g. `PointIdType`).
+#[derive(Debug, Default)]
+pub struct ScoreDetailAccumulator<Id> {
+    details: HashMap<Id, ScoreDetail>,
+}
+
+impl<Id: Eq + Hash + Clone> ScoreDetailAccumulator<Id> {
+    pub fn record_branch(&mut self, point: Id, branch: &str, score: f32, rank: usize) {
+        self.details
+            .entry(point)
+            .or_default()
+            .branches
+            .insert(branch.to_string(), BranchScore { score, rank });
+    }
+
+    pub fn record_fused(&mut self, point: &Id, fused: f32) {
+        if let Some(detail) = self.details.get_mut(point) {
+            detail.fused = fused;
+        }
+    }
+
+    pub fn into_details(self) -> HashMap<Id, ScoreDetail> {
+        self.details
+    }
+}
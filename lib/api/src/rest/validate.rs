@@ -1,37 +1,66 @@
 use std::borrow::Cow;
 
 use common::validation::validate_multi_vector;
-use validator::{Validate, ValidationError, ValidationErrors};
+use validator::{Validate, ValidateArgs, ValidationError, ValidationErrors};
 
-use super::schema::{BatchVectorStruct, Vector, VectorStruct};
+use super::inference::EmbedderRegistry;
+use super::schema::{BatchVectorStruct, Document, Vector, VectorStruct};
 use super::{
     ContextInput, Fusion, OrderByInterface, Query, QueryInterface, RecommendInput, Sample,
     VectorInput,
 };
 use crate::rest::NamedVectorStruct;
 
-impl Validate for VectorStruct {
-    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+/// Structural validation for a `Document` input: is there text to embed, and is `model` a name
+/// `registry` actually has an embedder for. This deliberately does *not* run inference — that's
+/// a potentially-blocking network call, wrong to do on the validation path — it only checks that
+/// running it later would be possible. The real embedding happens once, via
+/// [`super::inference::infer_vector`], in the upsert/query path that resolves `Document` into a
+/// stored or query vector.
+fn validate_document(
+    document: &Document,
+    registry: &EmbedderRegistry,
+) -> Result<(), ValidationErrors> {
+    if document.text.trim().is_empty() {
+        let mut errors = ValidationErrors::default();
+        let mut err = ValidationError::new("empty_document_text");
+        err.add_param(Cow::from("message"), &"document text must not be empty");
+        errors.add("text", err);
+        return Err(errors);
+    }
+
+    if registry.get(&document.model).is_none() {
+        let mut errors = ValidationErrors::default();
+        let mut err = ValidationError::new("unknown_embedder");
+        let message = format!(
+            "no embedder named {:?} is configured for this collection",
+            document.model
+        );
+        err.add_param(Cow::from("message"), &message);
+        errors.add("model", err);
+        return Err(errors);
+    }
+
+    Ok(())
+}
+
+impl<'a> ValidateArgs<'a> for VectorStruct {
+    type Args = &'a EmbedderRegistry;
+
+    fn validate_args(&self, registry: &'a EmbedderRegistry) -> Result<(), ValidationErrors> {
         match self {
             VectorStruct::Single(_) => Ok(()),
             VectorStruct::MultiDense(v) => validate_multi_vector(v),
             VectorStruct::Named(v) => common::validation::validate_iter(v.values()),
-            VectorStruct::Document(_) => {
-                let mut errors = ValidationErrors::default();
-                let mut err = ValidationError::new("not_supported_inference");
-                err.add_param(
-                    Cow::from("message"),
-                    &"Document inference is not implemented, please use vectors instead",
-                );
-                errors.add("text", err);
-                Err(errors)
-            }
+            VectorStruct::Document(document) => validate_document(document, registry),
         }
     }
 }
 
-impl Validate for BatchVectorStruct {
-    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+impl<'a> ValidateArgs<'a> for BatchVectorStruct {
+    type Args = &'a EmbedderRegistry;
+
+    fn validate_args(&self, registry: &'a EmbedderRegistry) -> Result<(), ValidationErrors> {
         match self {
             BatchVectorStruct::Single(_) => Ok(()),
             BatchVectorStruct::MultiDense(vectors) => {
@@ -43,36 +72,25 @@ impl Validate for BatchVectorStruct {
             BatchVectorStruct::Named(v) => {
                 common::validation::validate_iter(v.values().flat_map(|batch| batch.iter()))
             }
-            BatchVectorStruct::Document(_) => {
-                let mut errors = ValidationErrors::default();
-                let mut err = ValidationError::new("not_supported_inference");
-                err.add_param(
-                    Cow::from("message"),
-                    &"Document inference is not implemented, please use vectors instead",
-                );
-                errors.add("text", err);
-                Err(errors)
+            BatchVectorStruct::Document(documents) => {
+                for document in documents {
+                    validate_document(document, registry)?;
+                }
+                Ok(())
             }
         }
     }
 }
 
-impl Validate for Vector {
-    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+impl<'a> ValidateArgs<'a> for Vector {
+    type Args = &'a EmbedderRegistry;
+
+    fn validate_args(&self, registry: &'a EmbedderRegistry) -> Result<(), ValidationErrors> {
         match self {
             Vector::Dense(_) => Ok(()),
             Vector::Sparse(v) => v.validate(),
             Vector::MultiDense(m) => common::validation::validate_multi_vector(m),
-            Vector::Document(_) => {
-                let mut errors = ValidationErrors::default();
-                let mut err = ValidationError::new("not_supported_inference");
-                err.add_param(
-                    Cow::from("message"),
-                    &"Document inference is not implemented, please use vectors instead",
-                );
-                errors.add("text", err);
-                Err(errors)
-            }
+            Vector::Document(document) => validate_document(document, registry),
         }
     }
 }
@@ -87,22 +105,26 @@ impl Validate for NamedVectorStruct {
     }
 }
 
-impl Validate for QueryInterface {
-    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+impl<'a> ValidateArgs<'a> for QueryInterface {
+    type Args = &'a EmbedderRegistry;
+
+    fn validate_args(&self, registry: &'a EmbedderRegistry) -> Result<(), ValidationErrors> {
         match self {
-            QueryInterface::Nearest(vector) => vector.validate(),
-            QueryInterface::Query(query) => query.validate(),
+            QueryInterface::Nearest(vector) => vector.validate_args(registry),
+            QueryInterface::Query(query) => query.validate_args(registry),
         }
     }
 }
 
-impl Validate for Query {
-    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+impl<'a> ValidateArgs<'a> for Query {
+    type Args = &'a EmbedderRegistry;
+
+    fn validate_args(&self, registry: &'a EmbedderRegistry) -> Result<(), ValidationErrors> {
         match self {
-            Query::Nearest(vector) => vector.nearest.validate(),
-            Query::Recommend(recommend) => recommend.recommend.validate(),
-            Query::Discover(discover) => discover.discover.validate(),
-            Query::Context(context) => context.context.validate(),
+            Query::Nearest(vector) => vector.nearest.validate_args(registry),
+            Query::Recommend(recommend) => recommend.recommend.validate_args(registry),
+            Query::Discover(discover) => discover.discover.validate_args(registry),
+            Query::Context(context) => context.context.validate_args(registry),
             Query::Fusion(fusion) => fusion.fusion.validate(),
             Query::OrderBy(order_by) => order_by.order_by.validate(),
             Query::Sample(sample) => sample.sample.validate(),
@@ -110,29 +132,24 @@ impl Validate for Query {
     }
 }
 
-impl Validate for VectorInput {
-    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+impl<'a> ValidateArgs<'a> for VectorInput {
+    type Args = &'a EmbedderRegistry;
+
+    fn validate_args(&self, registry: &'a EmbedderRegistry) -> Result<(), ValidationErrors> {
         match self {
             VectorInput::Id(_id) => Ok(()),
             VectorInput::DenseVector(_dense) => Ok(()),
             VectorInput::SparseVector(sparse) => sparse.validate(),
             VectorInput::MultiDenseVector(multi) => validate_multi_vector(multi),
-            VectorInput::Document(_) => {
-                let mut errors = ValidationErrors::default();
-                let mut err = ValidationError::new("not_supported_inference");
-                err.add_param(
-                    Cow::from("message"),
-                    &"Document inference is not implemented, please use vectors instead",
-                );
-                errors.add("text", err);
-                Err(errors)
-            }
+            VectorInput::Document(document) => validate_document(document, registry),
         }
     }
 }
 
-impl Validate for RecommendInput {
-    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+impl<'a> ValidateArgs<'a> for RecommendInput {
+    type Args = &'a EmbedderRegistry;
+
+    fn validate_args(&self, registry: &'a EmbedderRegistry) -> Result<(), ValidationErrors> {
         let no_positives = self.positive.as_ref().map(|p| p.is_empty()).unwrap_or(true);
         let no_negatives = self.negative.as_ref().map(|n| n.is_empty()).unwrap_or(true);
 
@@ -148,17 +165,19 @@ impl Validate for RecommendInput {
         }
 
         for item in self.iter() {
-            item.validate()?;
+            item.validate_args(registry)?;
         }
 
         Ok(())
     }
 }
 
-impl Validate for ContextInput {
-    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+impl<'a> ValidateArgs<'a> for ContextInput {
+    type Args = &'a EmbedderRegistry;
+
+    fn validate_args(&self, registry: &'a EmbedderRegistry) -> Result<(), ValidationErrors> {
         for item in self.0.iter().flatten().flat_map(|item| item.iter()) {
-            item.validate()?;
+            item.validate_args(registry)?;
         }
 
         Ok(())
@@ -169,6 +188,19 @@ impl Validate for Fusion {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
             Fusion::Rrf | Fusion::Dbsf => Ok(()),
+            Fusion::Weighted { semantic_ratio } => {
+                if !(0.0..=1.0).contains(semantic_ratio) {
+                    let mut errors = ValidationErrors::default();
+                    let mut err = ValidationError::new("range");
+                    err.add_param(
+                        Cow::from("message"),
+                        &"semantic_ratio must be between 0.0 and 1.0",
+                    );
+                    errors.add("semantic_ratio", err);
+                    return Err(errors);
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -185,7 +217,8 @@ impl Validate for OrderByInterface {
 impl Validate for Sample {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
-            Sample::Random => Ok(()),
+            // Any seed value is valid; it only pins the RNG, it doesn't affect cardinality.
+            Sample::Random { seed: _ } => Ok(()),
         }
     }
 }
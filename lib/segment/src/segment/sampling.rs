@@ -1,18 +1,30 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use common::iterator_ext::IteratorExt;
+use rand::rngs::StdRng;
 use rand::seq::{IteratorRandom, SliceRandom};
+use rand::SeedableRng;
 
 use super::Segment;
 use crate::index::PayloadIndex;
 use crate::types::{Filter, PointIdType};
 
+/// Builds the RNG backing a random sampling pass: seeded and reproducible when `seed` is given,
+/// otherwise drawn from entropy like the old `thread_rng()` behaviour.
+fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
 impl Segment {
     pub(super) fn filtered_read_by_index_shuffled(
         &self,
         limit: usize,
         condition: &Filter,
         is_stopped: &AtomicBool,
+        seed: Option<u64>,
     ) -> Vec<PointIdType> {
         let payload_index = self.payload_index.borrow();
         let id_tracker = self.id_tracker.borrow();
@@ -23,7 +35,7 @@ impl Segment {
             .check_stop(|| is_stopped.load(Ordering::Relaxed))
             .filter_map(|internal_id| id_tracker.external_id(internal_id));
 
-        let mut rng = rand::thread_rng();
+        let mut rng = seeded_rng(seed);
         let mut shuffled = ids_iterator.choose_multiple(&mut rng, limit);
         shuffled.shuffle(&mut rng);
 
@@ -35,12 +47,13 @@ impl Segment {
         limit: usize,
         condition: &Filter,
         is_stopped: &AtomicBool,
+        seed: Option<u64>,
     ) -> Vec<PointIdType> {
         let payload_index = self.payload_index.borrow();
         let filter_context = payload_index.filter_context(condition);
         self.id_tracker
             .borrow()
-            .iter_random()
+            .iter_random(seed)
             .check_stop(|| is_stopped.load(Ordering::Relaxed))
             .filter(move |(_, internal_id)| filter_context.check(*internal_id))
             .map(|(external_id, _)| external_id)
@@ -48,12 +61,48 @@ impl Segment {
             .collect()
     }
 
-    pub(super) fn read_by_random_id(&self, limit: usize) -> Vec<PointIdType> {
+    pub(super) fn read_by_random_id(&self, limit: usize, seed: Option<u64>) -> Vec<PointIdType> {
         self.id_tracker
             .borrow()
-            .iter_random()
+            .iter_random(seed)
             .map(|x| x.0)
             .take(limit)
             .collect()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    use super::seeded_rng;
+
+    #[test]
+    fn same_seed_yields_same_sequence() {
+        let mut a = seeded_rng(Some(42));
+        let mut b = seeded_rng(Some(42));
+
+        let sequence_a: Vec<u32> = (0..16).map(|_| a.gen()).collect();
+        let sequence_b: Vec<u32> = (0..16).map(|_| b.gen()).collect();
+
+        assert_eq!(sequence_a, sequence_b);
+    }
+
+    #[test]
+    fn same_seed_yields_same_shuffle() {
+        let mut items_a: Vec<u32> = (0..32).collect();
+        let mut items_b = items_a.clone();
+
+        items_a.shuffle(&mut seeded_rng(Some(7)));
+        items_b.shuffle(&mut seeded_rng(Some(7)));
+
+        assert_eq!(items_a, items_b);
+    }
+
+    #[test]
+    fn no_seed_does_not_panic_and_still_shuffles() {
+        let mut items: Vec<u32> = (0..32).collect();
+        items.shuffle(&mut seeded_rng(None));
+    }
+}